@@ -1,24 +1,74 @@
 use std::fs;
-use std::io::{self, Write};
+use std::io;
 
 use anyhow::Result;
 use clap::Parser;
 
-use interpreter::interpret;
+use interpreter::{interpret_with_io, parse_tokens, tokenize, ArithmeticMode, Config, TapeConfig};
 
 #[derive(Parser, Debug)]
 #[clap(author, version, about, long_about = None)]
 struct Args {
     source_path: String,
+
+    /// Wrap cell values modulo 256 instead of erroring on overflow.
+    #[clap(long)]
+    wrap_cells: bool,
+
+    /// Print the tokenized source and exit without executing.
+    #[clap(long)]
+    dump_tokens: bool,
+
+    /// Print the parsed operation tree and exit without executing.
+    #[clap(long)]
+    dump_ops: bool,
+
+    /// Number of cells the tape starts with.
+    #[clap(long, default_value_t = 30_000)]
+    tape_size: usize,
+
+    /// Grow the tape with zero-filled cells instead of erroring once
+    /// `--tape-size` cells are exhausted.
+    #[clap(long)]
+    grow: bool,
+
+    /// Upper bound the tape may grow to when `--grow` is set.
+    #[clap(long)]
+    max_tape_size: Option<usize>,
 }
 
 fn main() -> Result<()> {
     let args = Args::parse();
     let source = fs::read_to_string(args.source_path)?;
+
+    if args.dump_tokens {
+        println!("{:#?}", tokenize(&source));
+        return Ok(());
+    }
+
+    if args.dump_ops {
+        println!("{:#?}", parse_tokens(tokenize(&source))?);
+        return Ok(());
+    }
+
     let stdin = io::stdin();
+    let stdout = io::stdout();
+
+    let config = Config {
+        arithmetic_mode: if args.wrap_cells {
+            ArithmeticMode::Wrapping
+        } else {
+            ArithmeticMode::Checked
+        },
+        tape: TapeConfig {
+            size: args.tape_size,
+            grow: args.grow,
+            max_size: args.max_tape_size,
+        },
+        ..Config::default()
+    };
 
-    let result = interpret(&source, Box::new(stdin))?;
-    io::stdout().write_all(result.as_bytes())?;
+    interpret_with_io(&source, Box::new(stdin), Box::new(stdout.lock()), config)?;
 
     Ok(())
 }
@@ -1,13 +1,91 @@
-use std::collections::LinkedList;
-use std::io;
+#![cfg_attr(not(feature = "std"), no_std)]
 
+extern crate alloc;
+
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+#[cfg(feature = "std")]
 use thiserror::Error;
 
-const MEMORY_SIZE: usize = 30_000;
+/// Re-exports the `Read`/`Write` traits the interpreter needs from either
+/// `std::io` or, without the `std` feature, `core2::io`, so the rest of the
+/// crate can stay agnostic of which one is active.
+#[cfg(feature = "std")]
+mod io_compat {
+    pub use std::io::{Error, Read, Write};
+}
+
+#[cfg(not(feature = "std"))]
+mod io_compat {
+    pub use core2::io::{Error, Read, Write};
+}
+
+use io_compat::{Error as IoError, Read, Write};
+
+const DEFAULT_TAPE_SIZE: usize = 30_000;
+
+/// Controls how `Operation::Increment`/`Decrement` behave when a cell
+/// crosses the 0/255 boundary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ArithmeticMode {
+    /// Increment/decrement past 0 or 255 is an error (`MemoryOverflow`).
+    #[default]
+    Checked,
+    /// Increment/decrement wraps around modulo 256, per the canonical
+    /// Brainfuck spec.
+    Wrapping,
+}
+
+/// Controls what happens to the current cell when `,` is executed but
+/// stdin has no more bytes to give.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EofPolicy {
+    /// Writes `0` into the current cell (the common convention).
+    #[default]
+    WriteZero,
+    /// Leaves the current cell's value unchanged.
+    Unchanged,
+}
+
+/// Runtime configuration for an interpreted program.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Config {
+    pub arithmetic_mode: ArithmeticMode,
+    pub eof_policy: EofPolicy,
+    pub tape: TapeConfig,
+}
+
+/// Controls how many cells the tape starts with and whether it may grow
+/// past that size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TapeConfig {
+    /// Number of cells the tape is allocated with up front.
+    pub size: usize,
+    /// When `true`, `MoveRight` extends the tape with zero-filled cells
+    /// instead of returning `PointerOverflow` once `size` is exhausted.
+    pub grow: bool,
+    /// Upper bound the tape may grow to when `grow` is enabled. `None`
+    /// means the tape may grow without limit.
+    pub max_size: Option<usize>,
+}
+
+impl Default for TapeConfig {
+    fn default() -> Self {
+        Self {
+            size: DEFAULT_TAPE_SIZE,
+            grow: false,
+            max_size: None,
+        }
+    }
+}
 
 // Token
 #[derive(PartialEq, Eq, Debug)]
-enum Token {
+pub enum Token {
     MoveRight,
     MoveLeft,
     Increment,
@@ -20,7 +98,7 @@ enum Token {
 }
 
 #[derive(PartialEq, Eq, Debug)]
-enum Operation {
+pub enum Operation {
     MoveRight(usize),
     MoveLeft(usize),
     Increment(u8),
@@ -28,45 +106,91 @@ enum Operation {
     Output,
     Input,
     Loop(Vec<Operation>),
+    /// Unconditionally assigns `0` to the current cell. Folded from a
+    /// `[-]`/`[+]`-shaped loop by [`optimize`].
+    SetZero,
+    /// Adds `cell[pointer] * factor` to `cell[pointer + offset]` for each
+    /// `(offset, factor)` pair, then zeroes the current cell. Folded from a
+    /// balanced copy/multiply loop by [`optimize`].
+    MultiplyAdd(Vec<(isize, u8)>),
 }
 
 // custom error type
-#[derive(Debug, Error)]
+#[cfg_attr(feature = "std", derive(Error))]
+#[derive(Debug)]
 pub enum InterpreterError {
-    #[error("Error parsing source: `{0}`")]
+    #[cfg_attr(feature = "std", error("Error parsing source: `{0}`"))]
     ParseError(String),
-    #[error("Memory overflow")]
+    #[cfg_attr(feature = "std", error("Memory overflow"))]
     MemoryOverflow,
-    #[error("Pointer is out of memory bounds")]
+    #[cfg_attr(feature = "std", error("Pointer is out of memory bounds"))]
     PointerOverflow,
-    #[error("Error reading from stdin: `{0}`")]
-    StdinError(io::Error),
+    #[cfg_attr(feature = "std", error("Error reading from stdin: `{0}`"))]
+    StdinError(IoError),
+    #[cfg_attr(feature = "std", error("Error writing to stdout: `{0}`"))]
+    StdoutError(IoError),
 }
 
+#[cfg(not(feature = "std"))]
+impl core::fmt::Display for InterpreterError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            InterpreterError::ParseError(message) => {
+                write!(f, "Error parsing source: `{message}`")
+            }
+            InterpreterError::MemoryOverflow => write!(f, "Memory overflow"),
+            InterpreterError::PointerOverflow => write!(f, "Pointer is out of memory bounds"),
+            InterpreterError::StdinError(err) => write!(f, "Error reading from stdin: `{err}`"),
+            InterpreterError::StdoutError(err) => write!(f, "Error writing to stdout: `{err}`"),
+        }
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl core::error::Error for InterpreterError {}
+
 struct Program<'a> {
-    memory: [u8; MEMORY_SIZE],
+    memory: Vec<u8>,
     pointer: usize,
-    stdin: Box<dyn io::Read + 'a>,
-    stdout: String,
+    stdin: Box<dyn Read + 'a>,
+    stdout: Box<dyn Write + 'a>,
+    config: Config,
 }
 
-// The constructor must accept stdin and build an empty program state:
+// The constructor must accept stdin/stdout and build an empty program state:
 impl<'a> Program<'a> {
-    fn new(stdin: Box<dyn io::Read + 'a>) -> Self {
+    fn new(stdin: Box<dyn Read + 'a>, stdout: Box<dyn Write + 'a>, config: Config) -> Self {
         Self {
-            memory: [0u8; MEMORY_SIZE],
+            memory: alloc::vec![0u8; config.tape.size],
             pointer: 0,
             stdin,
-            stdout: String::new(),
+            stdout,
+            config,
         }
     }
 }
 
 // execution of each operation
 impl<'a> Program<'a> {
-    fn execute(mut self, operations: &[Operation]) -> Result<String, InterpreterError> {
-        self.process_operations(operations)?;
-        Ok(self.stdout)
+    fn execute(mut self, operations: &[Operation]) -> Result<(), InterpreterError> {
+        self.process_operations(operations)
+    }
+
+    /// Extends the tape with zero-filled cells up to `required` when
+    /// `TapeConfig::grow` is enabled, bailing out with `PointerOverflow` if
+    /// growth is disabled or would exceed `TapeConfig::max_size`.
+    fn grow_to(&mut self, required: usize) -> Result<(), InterpreterError> {
+        if !self.config.tape.grow {
+            return Err(InterpreterError::PointerOverflow);
+        }
+
+        let limit = self.config.tape.max_size.unwrap_or(usize::MAX);
+        if required > limit {
+            return Err(InterpreterError::PointerOverflow);
+        }
+
+        self.memory.resize(required, 0);
+        Ok(())
     }
 
     fn process_operations(&mut self, operations: &[Operation]) -> Result<(), InterpreterError> {
@@ -84,32 +208,71 @@ impl<'a> Program<'a> {
                         .checked_add(*count)
                         .ok_or(InterpreterError::PointerOverflow)?;
                     if self.pointer >= self.memory.len() {
-                        return Err(InterpreterError::PointerOverflow);
+                        self.grow_to(self.pointer + 1)?;
                     }
                 }
                 Operation::Increment(count) => {
-                    self.memory[self.pointer] = self.memory[self.pointer]
-                        .checked_add(*count)
-                        .ok_or(InterpreterError::MemoryOverflow)?;
+                    self.memory[self.pointer] = match self.config.arithmetic_mode {
+                        ArithmeticMode::Wrapping => self.memory[self.pointer].wrapping_add(*count),
+                        ArithmeticMode::Checked => self.memory[self.pointer]
+                            .checked_add(*count)
+                            .ok_or(InterpreterError::MemoryOverflow)?,
+                    }
                 }
                 Operation::Decrement(count) => {
-                    self.memory[self.pointer] = self.memory[self.pointer]
-                        .checked_sub(*count)
-                        .ok_or(InterpreterError::MemoryOverflow)?
+                    self.memory[self.pointer] = match self.config.arithmetic_mode {
+                        ArithmeticMode::Wrapping => self.memory[self.pointer].wrapping_sub(*count),
+                        ArithmeticMode::Checked => self.memory[self.pointer]
+                            .checked_sub(*count)
+                            .ok_or(InterpreterError::MemoryOverflow)?,
+                    }
                 }
                 Operation::Input => {
                     let mut buf = [0u8];
-                    if let Err(err) = self.stdin.read(&mut buf) {
-                        return Err(InterpreterError::StdinError(err));
+                    let bytes_read = self
+                        .stdin
+                        .read(&mut buf)
+                        .map_err(InterpreterError::StdinError)?;
+                    match (bytes_read, self.config.eof_policy) {
+                        (0, EofPolicy::WriteZero) => self.memory[self.pointer] = 0,
+                        (0, EofPolicy::Unchanged) => {}
+                        _ => self.memory[self.pointer] = buf[0],
                     }
-                    self.memory[self.pointer] = buf[0] as u8;
                 }
-                Operation::Output => self.stdout.push(self.memory[self.pointer] as char),
+                Operation::Output => self
+                    .stdout
+                    .write_all(&[self.memory[self.pointer]])
+                    .map_err(InterpreterError::StdoutError)?,
                 Operation::Loop(operations) => {
                     while self.memory[self.pointer] != 0 {
                         self.process_operations(operations)?;
                     }
                 }
+                Operation::SetZero => self.memory[self.pointer] = 0,
+                Operation::MultiplyAdd(deltas) => {
+                    let value = self.memory[self.pointer];
+                    // The folded loop never runs its body on a zero cell.
+                    if value == 0 {
+                        continue;
+                    }
+                    for (offset, factor) in deltas {
+                        let target = self
+                            .pointer
+                            .checked_add_signed(*offset)
+                            .ok_or(InterpreterError::PointerOverflow)?;
+                        if target >= self.memory.len() {
+                            self.grow_to(target + 1)?;
+                        }
+                        let add = value.wrapping_mul(*factor);
+                        self.memory[target] = match self.config.arithmetic_mode {
+                            ArithmeticMode::Wrapping => self.memory[target].wrapping_add(add),
+                            ArithmeticMode::Checked => self.memory[target]
+                                .checked_add(add)
+                                .ok_or(InterpreterError::MemoryOverflow)?,
+                        };
+                    }
+                    self.memory[self.pointer] = 0;
+                }
             }
         }
 
@@ -120,10 +283,11 @@ impl<'a> Program<'a> {
 // Parsing Logic
 // - Parse &str into Vec<Token>
 // - Parse Vec<Token> into Vec<Operation>
-fn parse_source(source: &str) -> Result<Vec<Operation>, InterpreterError> {
-    // Convert characters to defined tokens,
-    // then skip all undefined characters using Token::Unknown.
-    let tokens = source
+
+/// Converts source characters into tokens, skipping anything that isn't a
+/// recognized Brainfuck instruction.
+pub fn tokenize(source: &str) -> Vec<Token> {
+    source
         .chars()
         .map(|cur| match cur {
             '>' => Token::MoveRight,
@@ -136,14 +300,25 @@ fn parse_source(source: &str) -> Result<Vec<Operation>, InterpreterError> {
             ']' => Token::LoopEnd,
             _ => Token::Unknown,
         })
-        .filter(|token| token.ne(&Token::Unknown));
+        .filter(|token| token.ne(&Token::Unknown))
+        .collect()
+}
 
+fn parse_source(source: &str) -> Result<Vec<Operation>, InterpreterError> {
+    parse_tokens(tokenize(source))
+}
+
+/// Parses a token stream into a run-length-folded operation tree,
+/// collapsing consecutive `+`/`-`/`<`/`>` tokens into a single operation.
+pub fn parse_tokens(
+    tokens: impl IntoIterator<Item = Token>,
+) -> Result<Vec<Operation>, InterpreterError> {
     // Convert Token to Operation with stack method
-    let mut stack: LinkedList<Vec<Operation>> = LinkedList::new();
-    stack.push_back(Vec::new());
+    let mut stack: Vec<Vec<Operation>> = Vec::new();
+    stack.push(Vec::new());
 
     for token in tokens {
-        let cur_operations = stack.back_mut().expect("Stack should not be empty!");
+        let cur_operations = stack.last_mut().expect("Stack should not be empty!");
         match token {
             Token::MoveRight => {
                 if let Some(Operation::MoveRight(x)) = cur_operations.last_mut() {
@@ -175,10 +350,10 @@ fn parse_source(source: &str) -> Result<Vec<Operation>, InterpreterError> {
             }
             Token::Input => cur_operations.push(Operation::Input),
             Token::Output => cur_operations.push(Operation::Output),
-            Token::LoopBegin => stack.push_back(Vec::new()),
+            Token::LoopBegin => stack.push(Vec::new()),
             Token::LoopEnd => {
-                let cur_operations = stack.pop_back().unwrap();
-                let prev_operations = stack.back_mut().ok_or_else(|| {
+                let cur_operations = stack.pop().unwrap();
+                let prev_operations = stack.last_mut().ok_or_else(|| {
                     InterpreterError::ParseError(String::from("Unexpected end of loop"))
                 })?;
 
@@ -193,7 +368,7 @@ fn parse_source(source: &str) -> Result<Vec<Operation>, InterpreterError> {
         }
     }
 
-    let operations = stack.pop_back().unwrap();
+    let operations = stack.pop().unwrap();
     if !stack.is_empty() {
         Err(InterpreterError::ParseError(String::from(
             "Expected end of loop",
@@ -203,18 +378,126 @@ fn parse_source(source: &str) -> Result<Vec<Operation>, InterpreterError> {
     }
 }
 
+/// Post-parse optimization pass. Recognizes two common loop shapes and
+/// folds them into dedicated operations that execute in one step instead
+/// of byte-by-byte:
+/// - `[-]`/`[+]`-style loops (a single coprime-to-256 increment/decrement)
+///   become [`Operation::SetZero`].
+/// - balanced copy/multiply loops (no I/O, no nested loops, moves that net
+///   to zero displacement, current cell decremented by exactly one per
+///   iteration) become a single [`Operation::MultiplyAdd`], which also
+///   zeroes the current cell.
+///
+/// Loops that match neither shape are recursed into but otherwise left
+/// untouched.
+///
+/// Both folds assume the loop would actually run to completion by
+/// wrapping through zero, which only holds under [`ArithmeticMode::Wrapping`].
+/// Under [`ArithmeticMode::Checked`] only the strictly safe `[-]` shape
+/// (decrement by exactly one) is folded, so a program that relies on
+/// overflowing still observes `MemoryOverflow` at the same point it would
+/// unoptimized.
+fn optimize(operations: Vec<Operation>, mode: ArithmeticMode) -> Vec<Operation> {
+    operations
+        .into_iter()
+        .map(|operation| match operation {
+            Operation::Loop(body) => optimize_loop(optimize(body, mode), mode),
+            other => other,
+        })
+        .collect()
+}
+
+fn optimize_loop(body: Vec<Operation>, mode: ArithmeticMode) -> Operation {
+    if let [Operation::Decrement(1)] = body.as_slice() {
+        return Operation::SetZero;
+    }
+
+    if mode == ArithmeticMode::Wrapping {
+        if let [Operation::Increment(n)] | [Operation::Decrement(n)] = body.as_slice() {
+            if n % 2 == 1 {
+                // Odd values are the only units mod 256, so repeatedly
+                // applying one is guaranteed to eventually land on zero.
+                return Operation::SetZero;
+            }
+        }
+
+        if let Some(multiply_add) = fold_multiply_add(&body) {
+            return multiply_add;
+        }
+    }
+
+    Operation::Loop(body)
+}
+
+fn fold_multiply_add(body: &[Operation]) -> Option<Operation> {
+    let mut pointer_offset: isize = 0;
+    let mut net_deltas: BTreeMap<isize, i32> = BTreeMap::new();
+
+    for operation in body {
+        match operation {
+            Operation::MoveRight(count) => pointer_offset += *count as isize,
+            Operation::MoveLeft(count) => pointer_offset -= *count as isize,
+            Operation::Increment(count) => {
+                *net_deltas.entry(pointer_offset).or_insert(0) += *count as i32
+            }
+            Operation::Decrement(count) => {
+                *net_deltas.entry(pointer_offset).or_insert(0) -= *count as i32
+            }
+            // I/O and nested loops can't be folded into a single step.
+            Operation::Output | Operation::Input | Operation::Loop(_) => return None,
+            Operation::SetZero | Operation::MultiplyAdd(_) => return None,
+        }
+    }
+
+    if pointer_offset != 0 {
+        return None;
+    }
+
+    let self_delta = net_deltas.remove(&0)?;
+    if self_delta.rem_euclid(256) != 255 {
+        return None;
+    }
+
+    let deltas = net_deltas
+        .into_iter()
+        .map(|(offset, delta)| (offset, delta.rem_euclid(256) as u8))
+        .collect();
+    Some(Operation::MultiplyAdd(deltas))
+}
+
+/// Convenience wrapper that runs `source` to completion and collects
+/// everything it writes to stdout into a `String`. Bytes that are not
+/// valid UTF-8 are replaced, so callers that need exact output bytes
+/// should use [`interpret_with_io`] instead.
 pub fn interpret<'a>(
     source: &'a str,
-    stdin: Box<dyn io::Read + 'a>,
+    stdin: Box<dyn Read + 'a>,
+    config: Config,
 ) -> Result<String, InterpreterError> {
-    let operations = parse_source(source)?;
-    let program = Program::new(stdin);
+    let mut stdout = Vec::new();
+    interpret_with_io(source, stdin, Box::new(&mut stdout), config)?;
+    Ok(String::from_utf8_lossy(&stdout).into_owned())
+}
+
+/// Runs `source` to completion, reading from `stdin` and writing raw
+/// output bytes to `stdout` as they are produced.
+pub fn interpret_with_io<'a>(
+    source: &'a str,
+    stdin: Box<dyn Read + 'a>,
+    stdout: Box<dyn Write + 'a>,
+    config: Config,
+) -> Result<(), InterpreterError> {
+    let operations = optimize(parse_source(source)?, config.arithmetic_mode);
+    let program = Program::new(stdin, stdout, config);
     program.execute(&operations)
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod test {
-    use crate::{interpret, parse_source, InterpreterError, Operation};
+    use crate::{
+        interpret, interpret_with_io, optimize, parse_source, ArithmeticMode, Config, EofPolicy,
+        InterpreterError, Operation, TapeConfig,
+    };
 
     #[test]
     fn parse_cat() {
@@ -250,7 +533,7 @@ mod test {
         let input = "".as_bytes();
         let expected = String::from("Hello World!\n");
 
-        let actual = interpret(source, Box::new(input)).expect("It works");
+        let actual = interpret(source, Box::new(input), Config::default()).expect("It works");
         assert_eq!(expected, actual);
     }
 
@@ -260,7 +543,7 @@ mod test {
         let input = "I love programming!".as_bytes();
         let expected = String::from("I love programming!");
 
-        let actual = interpret(source, Box::new(input)).expect("It works");
+        let actual = interpret(source, Box::new(input), Config::default()).expect("It works");
         assert_eq!(expected, actual);
     }
 
@@ -280,7 +563,7 @@ mod test {
         let input = "".as_bytes();
         let expected = String::from("1, 1, 2, 3, 5, 8, 13, 21, 34, 55, 89");
 
-        let actual = interpret(source, Box::new(input)).expect("It works");
+        let actual = interpret(source, Box::new(input), Config::default()).expect("It works");
         assert_eq!(expected, actual);
     }
 
@@ -289,7 +572,7 @@ mod test {
         let source = ">><<<";
         let input = "".as_bytes();
 
-        let actual = interpret(source, Box::new(input));
+        let actual = interpret(source, Box::new(input), Config::default());
         assert!(matches!(actual, Err(InterpreterError::PointerOverflow)));
     }
 
@@ -298,7 +581,7 @@ mod test {
         let source = "+[>+]";
         let input = "".as_bytes();
 
-        let actual = interpret(source, Box::new(input));
+        let actual = interpret(source, Box::new(input), Config::default());
         assert!(matches!(actual, Err(InterpreterError::PointerOverflow)));
     }
 
@@ -307,7 +590,7 @@ mod test {
         let source = "+--";
         let input = "".as_bytes();
 
-        let actual = interpret(source, Box::new(input));
+        let actual = interpret(source, Box::new(input), Config::default());
         assert!(matches!(actual, Err(InterpreterError::MemoryOverflow)));
     }
 
@@ -316,7 +599,231 @@ mod test {
         let source = "+[+]";
         let input = "".as_bytes();
 
-        let actual = interpret(source, Box::new(input));
+        let actual = interpret(source, Box::new(input), Config::default());
         assert!(matches!(actual, Err(InterpreterError::MemoryOverflow)));
     }
+
+    #[test]
+    fn wrap_cells_instead_of_overflowing() {
+        let source = "-.+++.";
+        let input = "".as_bytes();
+        let config = Config {
+            arithmetic_mode: ArithmeticMode::Wrapping,
+            ..Config::default()
+        };
+        let mut stdout = Vec::new();
+
+        interpret_with_io(source, Box::new(input), Box::new(&mut stdout), config)
+            .expect("It works");
+        assert_eq!(stdout, vec![0xffu8, 0x02]);
+    }
+
+    #[test]
+    fn output_is_byte_accurate() {
+        // 200 `+` then `.` outputs byte 200, well above the ASCII range,
+        // which would be mangled if cast through `char`.
+        let source = "+"
+            .repeat(200)
+            .chars()
+            .chain(".".chars())
+            .collect::<String>();
+        let input = "".as_bytes();
+        let mut stdout = Vec::new();
+
+        interpret_with_io(
+            &source,
+            Box::new(input),
+            Box::new(&mut stdout),
+            Config::default(),
+        )
+        .expect("It works");
+        assert_eq!(stdout, vec![200u8]);
+    }
+
+    #[test]
+    fn input_at_eof_writes_zero_by_default() {
+        let source = ",.";
+        let input = "".as_bytes();
+
+        let actual = interpret(source, Box::new(input), Config::default()).expect("It works");
+        assert_eq!(actual, "\u{0}");
+    }
+
+    #[test]
+    fn input_at_eof_can_leave_cell_unchanged() {
+        let source = "+++,.";
+        let input = "".as_bytes();
+        let config = Config {
+            eof_policy: EofPolicy::Unchanged,
+            ..Config::default()
+        };
+
+        let actual = interpret(source, Box::new(input), config).expect("It works");
+        assert_eq!(actual, "\u{3}");
+    }
+
+    #[test]
+    fn optimize_folds_clear_loop_into_set_zero() {
+        let operations = parse_source("+++++[-]").expect("It works");
+        let optimized = optimize(operations, ArithmeticMode::Checked);
+        assert_eq!(optimized, vec![Operation::Increment(5), Operation::SetZero]);
+    }
+
+    #[test]
+    fn optimize_folds_copy_loop_into_multiply_add() {
+        let operations = parse_source("[->+>++<<]").expect("It works");
+        let optimized = optimize(operations, ArithmeticMode::Wrapping);
+        assert_eq!(
+            optimized,
+            vec![Operation::MultiplyAdd(vec![(1, 1), (2, 2)])]
+        );
+    }
+
+    #[test]
+    fn optimize_leaves_unbalanced_loop_untouched() {
+        let expected = parse_source("[->+]").expect("It works");
+        let optimized = optimize(
+            parse_source("[->+]").expect("It works"),
+            ArithmeticMode::Wrapping,
+        );
+        assert_eq!(optimized, expected);
+    }
+
+    #[test]
+    fn optimize_does_not_fold_overflow_prone_loops_under_checked_mode() {
+        // "[+]" only terminates by wrapping past 255, so folding it to
+        // `SetZero` under checked arithmetic would hide the overflow error
+        // the unoptimized loop is supposed to raise.
+        let unchanged = parse_source("+[+]").expect("It works");
+        let optimized = optimize(
+            parse_source("+[+]").expect("It works"),
+            ArithmeticMode::Checked,
+        );
+        assert_eq!(optimized, unchanged);
+
+        // Under wrapping semantics the same loop is safe to fold.
+        let wrapped = optimize(
+            parse_source("+[+]").expect("It works"),
+            ArithmeticMode::Wrapping,
+        );
+        assert_eq!(wrapped, vec![Operation::Increment(1), Operation::SetZero]);
+    }
+
+    #[test]
+    fn optimized_hello_world_matches_unoptimized_output() {
+        let source = "++++++++[>++++[>++>+++>+++>+<<<<-]>+>+>->>+[<]<-]>>.>---.+++++++..+++.>>.<-.<.+++.------.--------.>>+.>++.";
+        let input = "".as_bytes();
+
+        let unoptimized = {
+            let operations = parse_source(source).expect("It works");
+            let mut stdout = Vec::new();
+            let program =
+                crate::Program::new(Box::new(input), Box::new(&mut stdout), Config::default());
+            program.execute(&operations).expect("It works");
+            stdout
+        };
+
+        let optimized = interpret(source, Box::new("".as_bytes()), Config::default())
+            .expect("It works")
+            .into_bytes();
+
+        assert_eq!(unoptimized, optimized);
+    }
+
+    #[test]
+    fn optimized_fibonacci_matches_unoptimized_output() {
+        let source = "+++++++++++
+        >+>>>>++++++++++++++++++++++++++++++++++++++++++++
+        >++++++++++++++++++++++++++++++++<<<<<<[>[>>>>>>+>
+        +<<<<<<<-]>>>>>>>[<<<<<<<+>>>>>>>-]<[>++++++++++[-
+        <-[>>+>+<<<-]>>>[<<<+>>>-]+<[>[-]<[-]]>[<<[>>>+<<<
+        -]>>[-]]<<]>>>[>>+>+<<<-]>>>[<<<+>>>-]+<[>[-]<[-]]
+        >[<<+>>[-]]<<<<<<<]>>>>>[+++++++++++++++++++++++++
+        +++++++++++++++++++++++.[-]]++++++++++<[->-<]>++++
+        ++++++++++++++++++++++++++++++++++++++++++++.[-]<<
+        <<<<<<<<<<[>>>+>+<<<<-]>>>>[<<<<+>>>>-]<-[>>.>.<<<
+        [-]]<<[>>+>+<<<-]>>>[<<<+>>>-]<<[<+>-]>[<+>-]<<<-]";
+        let input = "".as_bytes();
+
+        let unoptimized = {
+            let operations = parse_source(source).expect("It works");
+            let mut stdout = Vec::new();
+            let program =
+                crate::Program::new(Box::new(input), Box::new(&mut stdout), Config::default());
+            program.execute(&operations).expect("It works");
+            stdout
+        };
+
+        let optimized = interpret(source, Box::new("".as_bytes()), Config::default())
+            .expect("It works")
+            .into_bytes();
+
+        assert_eq!(unoptimized, optimized);
+    }
+
+    #[test]
+    fn small_fixed_tape_overflows() {
+        let source = ">>>";
+        let input = "".as_bytes();
+        let config = Config {
+            tape: TapeConfig {
+                size: 2,
+                ..TapeConfig::default()
+            },
+            ..Config::default()
+        };
+
+        let actual = interpret(source, Box::new(input), config);
+        assert!(matches!(actual, Err(InterpreterError::PointerOverflow)));
+    }
+
+    #[test]
+    fn custom_tape_size_succeeds() {
+        let source = ">>>+.";
+        let input = "".as_bytes();
+        let config = Config {
+            tape: TapeConfig {
+                size: 4,
+                ..TapeConfig::default()
+            },
+            ..Config::default()
+        };
+
+        let actual = interpret(source, Box::new(input), config).expect("It works");
+        assert_eq!(actual, "\u{1}");
+    }
+
+    #[test]
+    fn growing_tape_expands_past_default_size() {
+        let source = ">".repeat(30_000) + "+.";
+        let input = "".as_bytes();
+        let config = Config {
+            tape: TapeConfig {
+                size: 1,
+                grow: true,
+                ..TapeConfig::default()
+            },
+            ..Config::default()
+        };
+
+        let actual = interpret(&source, Box::new(input), config).expect("It works");
+        assert_eq!(actual, "\u{1}");
+    }
+
+    #[test]
+    fn growing_tape_still_bounded_by_max_size() {
+        let source = ">>>";
+        let input = "".as_bytes();
+        let config = Config {
+            tape: TapeConfig {
+                size: 1,
+                grow: true,
+                max_size: Some(2),
+            },
+            ..Config::default()
+        };
+
+        let actual = interpret(source, Box::new(input), config);
+        assert!(matches!(actual, Err(InterpreterError::PointerOverflow)));
+    }
 }